@@ -0,0 +1,357 @@
+//! Proving backends for constraint systems.
+
+use std::collections::HashMap;
+use std::error;
+use std::io::{self, Read, Write};
+
+use ConstraintSystem;
+
+/// A proving system that can produce and check proofs of satisfiability for a
+/// [`ConstraintSystem`].
+///
+/// This allows external proving crates to be driven through a common
+/// interface.
+pub trait ProvingBackend<CS: ConstraintSystem> {
+    /// The error returned when setup, proving, or verification fails.
+    type Error: error::Error;
+
+    /// The key used to create proofs for a specific system.
+    type ProvingKey;
+
+    /// The key used to verify proofs for a specific system.
+    type VerifyingKey;
+
+    /// A proof that some assignment satisfies a system.
+    type Proof;
+
+    /// Returns a short identifier for this backend, such as `groth16-bls12381`.
+    fn name(&self) -> &'static str;
+
+    /// Generates the proving and verifying keys for `cs`.
+    fn setup(&self, cs: &CS) -> Result<(Self::ProvingKey, Self::VerifyingKey), Self::Error>;
+
+    /// Creates a proof that `assignment` satisfies `cs`.
+    ///
+    /// The proof is verified against `cs.instance(assignment)`.
+    fn prove(
+        &self,
+        pk: &Self::ProvingKey,
+        cs: &CS,
+        assignment: &CS::Assignment,
+    ) -> Result<Self::Proof, Self::Error>;
+
+    /// Returns `true` if `proof` is valid for `instance` under `vk`.
+    fn verify(
+        &self,
+        vk: &Self::VerifyingKey,
+        instance: &CS::Instance,
+        proof: &Self::Proof,
+    ) -> Result<bool, Self::Error>;
+}
+
+/// A key or proof produced by a [`ProvingBackend`] that has a byte encoding.
+pub trait Artifact: Sized {
+    /// Reads an encoded artifact from `reader`.
+    fn read<R: Read>(reader: R) -> io::Result<Self>;
+
+    /// Writes the encoding of this artifact to `writer`.
+    fn write<W: Write>(&self, writer: W) -> io::Result<()>;
+
+    /// Decodes an artifact from its encoding.
+    ///
+    /// Returns an error if `bytes` contains anything after the encoding.
+    fn decode(mut bytes: &[u8]) -> io::Result<Self> {
+        let artifact = Self::read(&mut bytes)?;
+        if bytes.is_empty() {
+            Ok(artifact)
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "trailing bytes after encoding",
+            ))
+        }
+    }
+
+    /// Returns the encoding of this artifact.
+    fn encode(&self) -> io::Result<Vec<u8>> {
+        let mut buf = vec![];
+        self.write(&mut buf)?;
+        Ok(buf)
+    }
+}
+
+/// The error returned by a [`DynBackend`].
+pub type BoxError = Box<dyn error::Error + Send + Sync>;
+
+/// An object-safe view of a [`ProvingBackend`], which exchanges keys and proofs
+/// as their encodings.
+///
+/// This is what a [`Registry`] holds, so that backends with different artifact
+/// types can be selected by name at runtime.
+pub trait DynBackend<CS: ConstraintSystem>: Send + Sync {
+    /// Returns the identifier of the underlying backend.
+    fn name(&self) -> &'static str;
+
+    /// Generates the encoded proving and verifying keys for `cs`.
+    fn setup(&self, cs: &CS) -> Result<(Vec<u8>, Vec<u8>), BoxError>;
+
+    /// Creates an encoded proof that `assignment` satisfies `cs`.
+    fn prove(&self, pk: &[u8], cs: &CS, assignment: &CS::Assignment) -> Result<Vec<u8>, BoxError>;
+
+    /// Returns `true` if the encoded `proof` is valid for `instance` under the
+    /// encoded `vk`.
+    fn verify(&self, vk: &[u8], instance: &CS::Instance, proof: &[u8]) -> Result<bool, BoxError>;
+}
+
+struct Erased<B>(B);
+
+impl<CS, B> DynBackend<CS> for Erased<B>
+where
+    CS: ConstraintSystem,
+    B: ProvingBackend<CS> + Send + Sync,
+    B::Error: Send + Sync + 'static,
+    B::ProvingKey: Artifact,
+    B::VerifyingKey: Artifact,
+    B::Proof: Artifact,
+{
+    fn name(&self) -> &'static str {
+        self.0.name()
+    }
+
+    fn setup(&self, cs: &CS) -> Result<(Vec<u8>, Vec<u8>), BoxError> {
+        let (pk, vk) = self.0.setup(cs)?;
+        Ok((pk.encode()?, vk.encode()?))
+    }
+
+    fn prove(&self, pk: &[u8], cs: &CS, assignment: &CS::Assignment) -> Result<Vec<u8>, BoxError> {
+        let pk = B::ProvingKey::decode(pk)?;
+        let proof = self.0.prove(&pk, cs, assignment)?;
+        Ok(proof.encode()?)
+    }
+
+    fn verify(&self, vk: &[u8], instance: &CS::Instance, proof: &[u8]) -> Result<bool, BoxError> {
+        let vk = B::VerifyingKey::decode(vk)?;
+        let proof = B::Proof::decode(proof)?;
+        Ok(self.0.verify(&vk, instance, &proof)?)
+    }
+}
+
+/// A set of proving backends for `CS`, looked up by [`ProvingBackend::name`].
+pub struct Registry<CS: ConstraintSystem> {
+    backends: HashMap<&'static str, Box<dyn DynBackend<CS>>>,
+}
+
+impl<CS: ConstraintSystem> Default for Registry<CS> {
+    fn default() -> Self {
+        Registry {
+            backends: HashMap::new(),
+        }
+    }
+}
+
+impl<CS: ConstraintSystem> Registry<CS> {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `backend` under its name, returning any backend that was
+    /// previously registered under the same name.
+    pub fn register<B>(&mut self, backend: B) -> Option<Box<dyn DynBackend<CS>>>
+    where
+        B: ProvingBackend<CS> + Send + Sync + 'static,
+        B::Error: Send + Sync + 'static,
+        B::ProvingKey: Artifact,
+        B::VerifyingKey: Artifact,
+        B::Proof: Artifact,
+    {
+        self.backends
+            .insert(backend.name(), Box::new(Erased(backend)))
+    }
+
+    /// Returns the backend registered under `name`, if any.
+    pub fn get(&self, name: &str) -> Option<&dyn DynBackend<CS>> {
+        self.backends.get(name).map(|backend| &**backend)
+    }
+
+    /// Returns the names of all registered backends, in sorted order.
+    pub fn names(&self) -> Vec<&'static str> {
+        let mut names: Vec<_> = self.backends.keys().cloned().collect();
+        names.sort();
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error;
+    use std::fmt;
+    use std::io::{self, Read, Write};
+
+    use super::{Artifact, ProvingBackend, Registry};
+    use test_utils::Products;
+    use ConstraintSystem;
+
+    #[derive(Debug)]
+    struct Unsatisfied;
+
+    impl fmt::Display for Unsatisfied {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "assignment does not satisfy the constraint system")
+        }
+    }
+
+    impl error::Error for Unsatisfied {}
+
+    /// The empty proving key of [`Cleartext`].
+    #[derive(Debug)]
+    struct NoKey;
+
+    /// A witness sent in the clear, as the proof of [`Cleartext`].
+    #[derive(Debug)]
+    struct Witness(Vec<u64>);
+
+    impl Artifact for NoKey {
+        fn read<R: Read>(_: R) -> io::Result<Self> {
+            Ok(NoKey)
+        }
+
+        fn write<W: Write>(&self, _: W) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Artifact for Products {
+        fn read<R: Read>(reader: R) -> io::Result<Self> {
+            <Products as ConstraintSystem>::read(reader)
+        }
+
+        fn write<W: Write>(&self, writer: W) -> io::Result<()> {
+            ConstraintSystem::write(self, writer)
+        }
+    }
+
+    impl Artifact for Witness {
+        fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+            let mut buf = vec![];
+            reader.read_to_end(&mut buf)?;
+            if buf.len() % 8 != 0 {
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+            Ok(Witness(
+                buf.chunks(8)
+                    .map(|chunk| {
+                        let mut word = [0; 8];
+                        word.copy_from_slice(chunk);
+                        u64::from_le_bytes(word)
+                    })
+                    .collect(),
+            ))
+        }
+
+        fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+            for v in &self.0 {
+                writer.write_all(&v.to_le_bytes())?;
+            }
+            Ok(())
+        }
+    }
+
+    /// A non-zero-knowledge backend whose proofs are the witness in the clear.
+    struct Cleartext;
+
+    impl ProvingBackend<Products> for Cleartext {
+        type Error = Unsatisfied;
+        type ProvingKey = NoKey;
+        type VerifyingKey = Products;
+        type Proof = Witness;
+
+        fn name(&self) -> &'static str {
+            "cleartext"
+        }
+
+        fn setup(&self, cs: &Products) -> Result<(NoKey, Products), Unsatisfied> {
+            Ok((NoKey, cs.clone()))
+        }
+
+        fn prove(&self, _: &NoKey, cs: &Products, z: &Vec<u64>) -> Result<Witness, Unsatisfied> {
+            if cs.is_satisfied_by(z) {
+                z.get(1 + cs.num_inputs as usize..)
+                    .map(|w| Witness(w.to_vec()))
+                    .ok_or(Unsatisfied)
+            } else {
+                Err(Unsatisfied)
+            }
+        }
+
+        fn verify(
+            &self,
+            vk: &Products,
+            instance: &Vec<u64>,
+            proof: &Witness,
+        ) -> Result<bool, Unsatisfied> {
+            let mut z = vec![1];
+            z.extend_from_slice(instance);
+            z.extend_from_slice(&proof.0);
+            Ok(instance.len() as u64 == vk.num_inputs && vk.is_satisfied_by(&z))
+        }
+    }
+
+    #[test]
+    fn prove_and_verify() {
+        let cs = Products::square();
+        let backend = Cleartext;
+        assert_eq!(backend.name(), "cleartext");
+
+        let (pk, vk) = backend.setup(&cs).unwrap();
+        let z = vec![1, 3, 2];
+        let proof = backend.prove(&pk, &cs, &z).unwrap();
+        let instance = cs.instance(&z);
+        assert_eq!(instance, vec![3]);
+        assert!(backend.verify(&vk, &instance, &proof).unwrap());
+        assert!(!backend.verify(&vk, &vec![5], &proof).unwrap());
+        assert!(backend.prove(&pk, &cs, &vec![1, 3, 3]).is_err());
+    }
+
+    #[test]
+    fn prove_short_assignment() {
+        // With no constraints, any assignment is satisfying, but one without
+        // room for the instance has no witness to prove with.
+        let cs = Products {
+            p: 7,
+            num_inputs: 1,
+            constraints: vec![],
+        };
+        let backend = Cleartext;
+        let (pk, _) = backend.setup(&cs).unwrap();
+        assert!(backend.prove(&pk, &cs, &vec![]).is_err());
+        assert!(backend.prove(&pk, &cs, &vec![1]).is_err());
+    }
+
+    #[test]
+    fn registry_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Registry<Products>>();
+    }
+
+    #[test]
+    fn registry_lookup() {
+        let cs = Products::square();
+        let mut registry = Registry::new();
+        assert!(registry.register(Cleartext).is_none());
+        assert_eq!(registry.names(), vec!["cleartext"]);
+        assert!(registry.get("groth16-bls12381").is_none());
+
+        let backend = registry.get("cleartext").unwrap();
+        assert_eq!(backend.name(), "cleartext");
+        let (pk, vk) = backend.setup(&cs).unwrap();
+        let z = vec![1, 3, 2];
+        let proof = backend.prove(&pk, &cs, &z).unwrap();
+        assert!(backend.verify(&vk, &cs.instance(&z), &proof).unwrap());
+        assert!(!backend.verify(&vk, &vec![5], &proof).unwrap());
+        assert!(backend.verify(&vk, &vec![3], &proof[1..]).is_err());
+        assert!(backend.prove(&pk, &cs, &vec![1, 3, 3]).is_err());
+
+        assert!(registry.register(Cleartext).is_some());
+    }
+}
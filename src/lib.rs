@@ -1,7 +1,112 @@
+//! Traits for implementing zero-knowledge proving systems.
+
+use std::error;
+use std::fmt;
+use std::io::{self, Read, Write};
+
+pub mod backend;
+
+#[cfg(test)]
+mod test_utils;
+
+/// A constraint system over a prime field.
+///
+/// Implementations describe a relation (R1CS, Plonkish, AIR, ...) that can be
+/// serialized, inspected, and checked against an assignment of its variables,
+/// so that tooling can handle different kinds of systems generically.
+pub trait ConstraintSystem: Sized {
+    /// The error returned when reading or writing the system fails.
+    type Error: error::Error + From<io::Error>;
+
+    /// A complete assignment of values to the variables of the system.
+    type Assignment: fmt::Debug;
+
+    /// The public inputs of the system, against which proofs are verified.
+    type Instance: fmt::Debug;
+
+    /// The representation of the field characteristic.
+    type Characteristic: Clone + fmt::Display + fmt::Debug + PartialEq;
+
+    /// Returns the number of constraints in the system.
+    fn num_constraints(&self) -> usize;
+
+    /// Returns the characteristic of the field the system is defined over.
+    fn field_characteristic(&self) -> &Self::Characteristic;
+
+    /// Returns `true` if every constraint is satisfied by `assignment`.
+    fn is_satisfied_by(&self, assignment: &Self::Assignment) -> bool;
+
+    /// Returns the public inputs contained in `assignment`.
+    fn instance(&self, assignment: &Self::Assignment) -> Self::Instance;
+
+    /// Reads an encoded system from `reader`.
+    fn read<R: Read>(reader: R) -> Result<Self, Self::Error>;
+
+    /// Writes the encoding of this system to `writer`.
+    fn write<W: Write>(&self, writer: W) -> Result<(), Self::Error>;
+
+    /// Decodes a system from its encoding.
+    ///
+    /// Returns an error if `bytes` contains anything after the encoding.
+    fn decode(mut bytes: &[u8]) -> Result<Self, Self::Error> {
+        let cs = Self::read(&mut bytes)?;
+        if bytes.is_empty() {
+            Ok(cs)
+        } else {
+            Err(io::Error::new(io::ErrorKind::InvalidData, "trailing bytes after encoding").into())
+        }
+    }
+
+    /// Returns the encoding of this system.
+    fn encode(&self) -> Result<Vec<u8>, Self::Error> {
+        let mut buf = vec![];
+        self.write(&mut buf)?;
+        Ok(buf)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::io;
+
+    use super::ConstraintSystem;
+    use test_utils::Products;
+
     #[test]
     fn it_works() {
         assert_eq!(2 + 2, 4);
     }
+
+    fn describe<CS: ConstraintSystem>(cs: &CS) -> String {
+        format!(
+            "{} constraints over F_{}",
+            cs.num_constraints(),
+            cs.field_characteristic()
+        )
+    }
+
+    #[test]
+    fn generic_codec_and_check() {
+        let cs = Products::square();
+        assert_eq!(cs.num_constraints(), 1);
+        assert_eq!(*cs.field_characteristic(), 7);
+        assert_eq!(describe(&cs), "1 constraints over F_7");
+
+        let encoded = cs.encode().unwrap();
+        assert_eq!(Products::decode(&encoded).unwrap(), cs);
+        assert!(Products::decode(&encoded[..encoded.len() - 1]).is_err());
+        let trailing = [&encoded[..], b"garbage"].concat();
+        assert!(Products::decode(&trailing).is_err());
+        let mut zero_p = encoded.clone();
+        zero_p[..8].copy_from_slice(&0u64.to_le_bytes());
+        assert_eq!(
+            Products::decode(&zero_p).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+
+        assert!(cs.is_satisfied_by(&vec![1, 3, 2]));
+        assert!(!cs.is_satisfied_by(&vec![1, 3, 3]));
+        assert!(!cs.is_satisfied_by(&vec![1, 3]));
+        assert_eq!(cs.instance(&vec![1, 3, 2]), vec![3]);
+    }
 }
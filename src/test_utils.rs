@@ -0,0 +1,104 @@
+//! Fixtures shared by the unit tests.
+
+use std::io::{self, Read, Write};
+
+use ConstraintSystem;
+
+/// A toy system of constraints `z[a] * z[b] = z[c] (mod p)`, over assignments
+/// `z = [1, x_1, ..., x_n, w_1, ...]` whose instance is `[x_1, ..., x_n]`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Products {
+    pub(crate) p: u64,
+    pub(crate) num_inputs: u64,
+    pub(crate) constraints: Vec<(u64, u64, u64)>,
+}
+
+impl Products {
+    /// Returns the system `x * x = w` over `F_7`, with `z = [1, x, w]`.
+    pub(crate) fn square() -> Self {
+        Products {
+            p: 7,
+            num_inputs: 1,
+            constraints: vec![(1, 1, 2)],
+        }
+    }
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut buf = [0; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+impl ConstraintSystem for Products {
+    type Error = io::Error;
+    type Assignment = Vec<u64>;
+    type Instance = Vec<u64>;
+    type Characteristic = u64;
+
+    fn num_constraints(&self) -> usize {
+        self.constraints.len()
+    }
+
+    fn field_characteristic(&self) -> &u64 {
+        &self.p
+    }
+
+    fn is_satisfied_by(&self, z: &Vec<u64>) -> bool {
+        let get = |i: u64| z.get(i as usize).copied();
+        self.constraints
+            .iter()
+            .all(|&(a, b, c)| match (get(a), get(b), get(c)) {
+                (Some(a), Some(b), Some(c)) => {
+                    (u128::from(a) * u128::from(b)) % u128::from(self.p) == u128::from(c % self.p)
+                }
+                _ => false,
+            })
+    }
+
+    fn instance(&self, z: &Vec<u64>) -> Vec<u64> {
+        z.iter()
+            .skip(1)
+            .take(self.num_inputs as usize)
+            .cloned()
+            .collect()
+    }
+
+    fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        let p = read_u64(&mut reader)?;
+        if p < 2 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "characteristic must be at least 2",
+            ));
+        }
+        let num_inputs = read_u64(&mut reader)?;
+        let n = read_u64(&mut reader)?;
+        let constraints = (0..n)
+            .map(|_| {
+                Ok((
+                    read_u64(&mut reader)?,
+                    read_u64(&mut reader)?,
+                    read_u64(&mut reader)?,
+                ))
+            })
+            .collect::<io::Result<_>>()?;
+        Ok(Products {
+            p,
+            num_inputs,
+            constraints,
+        })
+    }
+
+    fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(&self.p.to_le_bytes())?;
+        writer.write_all(&self.num_inputs.to_le_bytes())?;
+        writer.write_all(&(self.constraints.len() as u64).to_le_bytes())?;
+        for &(a, b, c) in &self.constraints {
+            writer.write_all(&a.to_le_bytes())?;
+            writer.write_all(&b.to_le_bytes())?;
+            writer.write_all(&c.to_le_bytes())?;
+        }
+        Ok(())
+    }
+}